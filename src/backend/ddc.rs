@@ -0,0 +1,78 @@
+//! Backend that controls external monitors over DDC/CI (I2C), using VCP feature code `0x10`
+//! ("brightness").
+
+use ddc::Ddc;
+
+use super::BackendController;
+use crate::Error;
+
+/// VCP feature code for "brightness" as defined by the DDC/CI/MCCS specification.
+const VCP_BRIGHTNESS: u8 = 0x10;
+
+const I2C_DEV_DIR: &str = "/dev";
+
+#[derive(Debug)]
+pub struct DdcController {
+	name: String,
+	max: u64,
+	value: u64,
+	handle: ddc_i2c::I2cDdc<i2c_linux::I2c<std::fs::File>>,
+}
+
+impl BackendController for DdcController {
+	fn name(&self) -> &str {
+		&self.name
+	}
+
+	fn max(&self) -> u64 {
+		self.max
+	}
+
+	fn raw(&self) -> u64 {
+		self.value
+	}
+
+	fn set_raw(&mut self, raw: u64) -> Result<(), Error> {
+		let raw = raw.clamp(0, self.max);
+		self.handle.set_vcp_feature(VCP_BRIGHTNESS, raw as u16)
+			.map_err(|e| Error::Backend(format!("failed to set brightness on {}: {e}", self.name)))?;
+		self.value = raw;
+		Ok(())
+	}
+}
+
+/// List the I2C buses that have a monitor responding to the DDC/CI brightness query.
+pub fn list() -> Result<Vec<String>, Error> {
+	let dir = std::fs::read_dir(I2C_DEV_DIR)
+		.map_err(|e| Error::Io { path: I2C_DEV_DIR.into(), source: e })?;
+
+	let mut names = Vec::new();
+	for entry in dir {
+		let entry = entry
+			.map_err(|e| Error::Io { path: I2C_DEV_DIR.into(), source: e })?;
+		let name = entry.file_name().to_string_lossy().into_owned();
+		if name.starts_with("i2c-") && open_by_name(&name).is_ok() {
+			names.push(name);
+		}
+	}
+	Ok(names)
+}
+
+pub fn open_by_name(name: &str) -> Result<DdcController, Error> {
+	let path = std::path::Path::new(I2C_DEV_DIR).join(name);
+	log::debug!("Opening DDC/CI controller with path: {}", path.display());
+
+	let i2c = i2c_linux::I2c::from_path(&path)
+		.map_err(|e| Error::Io { path, source: e })?;
+	let mut handle = ddc_i2c::I2cDdc::new(i2c);
+
+	let feature = handle.get_vcp_feature(VCP_BRIGHTNESS)
+		.map_err(|e| Error::Backend(format!("failed to query brightness of {name} over DDC/CI: {e}")))?;
+
+	Ok(DdcController {
+		name: name.to_owned(),
+		max: feature.maximum().into(),
+		value: feature.value().into(),
+		handle,
+	})
+}
@@ -0,0 +1,84 @@
+//! Backend that asks `logind` to change the brightness over D-Bus, so unprivileged users don't
+//! need a udev rule granting write access to `/sys/class/backlight`.
+
+use super::sysfs;
+use super::BackendController;
+use crate::Error;
+
+/// The `logind` subsystem name for backlight devices.
+const SUBSYSTEM: &str = "backlight";
+
+#[derive(Debug)]
+pub struct LogindController {
+	name: String,
+	max: u64,
+	value: u64,
+	session: zbus::blocking::Proxy<'static>,
+}
+
+impl BackendController for LogindController {
+	fn name(&self) -> &str {
+		&self.name
+	}
+
+	fn max(&self) -> u64 {
+		self.max
+	}
+
+	fn raw(&self) -> u64 {
+		self.value
+	}
+
+	fn set_raw(&mut self, raw: u64) -> Result<(), Error> {
+		let raw = raw.clamp(0, self.max);
+		self.session.call_method("SetBrightness", &(SUBSYSTEM, self.name.as_str(), raw as u32))
+			.map_err(|e| Error::Backend(format!("failed to set brightness of {} through logind: {e}", self.name)))?;
+		self.value = raw;
+		Ok(())
+	}
+}
+
+/// List the backlight controllers that logind could plausibly control.
+///
+/// These are the same devices exposed by [`super::sysfs`]; only the *write* path goes through
+/// D-Bus instead of the sysfs file.
+pub fn list() -> Result<Vec<String>, Error> {
+	sysfs::list()
+}
+
+pub fn open_by_name(name: &str) -> Result<LogindController, Error> {
+	// Read the current/max value through a read-only open: `sysfs::open_by_name` opens
+	// `brightness` for writing too, which is exactly the access unprivileged users running this
+	// backend don't have.
+	let (value, max) = sysfs::read_only_by_name(name)?;
+	let session = current_session_proxy()?;
+
+	Ok(LogindController {
+		name: name.to_owned(),
+		max,
+		value,
+		session,
+	})
+}
+
+/// Get a D-Bus proxy for the `org.freedesktop.login1.Session` of the current login session.
+fn current_session_proxy() -> Result<zbus::blocking::Proxy<'static>, Error> {
+	let connection = zbus::blocking::Connection::system()
+		.map_err(|e| Error::Backend(format!("failed to connect to the system D-Bus: {e}")))?;
+
+	let session_id = std::env::var("XDG_SESSION_ID")
+		.map_err(|_| Error::Backend("the XDG_SESSION_ID environment variable is not set, can not determine the current login session".into()))?;
+
+	let manager = zbus::blocking::Proxy::new(
+		&connection,
+		"org.freedesktop.login1",
+		"/org/freedesktop/login1",
+		"org.freedesktop.login1.Manager",
+	).map_err(|e| Error::Backend(format!("failed to create D-Bus proxy for logind: {e}")))?;
+
+	let path: zbus::zvariant::OwnedObjectPath = manager.call("GetSession", &(session_id.as_str(),))
+		.map_err(|e| Error::Backend(format!("failed to look up logind session {session_id}: {e}")))?;
+
+	zbus::blocking::Proxy::new(&connection, "org.freedesktop.login1", path, "org.freedesktop.login1.Session")
+		.map_err(|e| Error::Backend(format!("failed to create D-Bus proxy for logind session {session_id}: {e}")))
+}
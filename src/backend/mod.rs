@@ -0,0 +1,115 @@
+//! Abstraction over the different ways a display's brightness can be read and changed.
+//!
+//! Three backends are currently supported:
+//!
+//! * [`Backend::Sysfs`]: reads and writes `/sys/class/backlight` directly. Works for laptop
+//!   panels, but usually needs root or a udev rule to write without privileges.
+//! * [`Backend::Ddc`]: talks DDC/CI over I2C to control external monitors.
+//! * [`Backend::Logind`]: asks `logind` over D-Bus to change the brightness on our behalf, which
+//!   works for unprivileged users without any udev rules.
+
+pub mod ddc;
+pub mod logind;
+pub mod sysfs;
+
+use crate::Error;
+
+/// A single open brightness controller, regardless of which backend it came from.
+pub trait BackendController: std::fmt::Debug {
+	/// The name of this controller, as accepted by [`Backend::open_by_name`].
+	fn name(&self) -> &str;
+
+	/// The maximum raw brightness value.
+	fn max(&self) -> u64;
+
+	/// The current raw brightness value.
+	fn raw(&self) -> u64;
+
+	/// Set the raw brightness value.
+	fn set_raw(&mut self, raw: u64) -> Result<(), Error>;
+}
+
+/// The backend to use for listing and opening brightness controllers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Backend {
+	/// Laptop and internal panels exposed through `/sys/class/backlight`.
+	Sysfs,
+	/// External monitors controlled over DDC/CI (I2C).
+	Ddc,
+	/// Any backlight controlled through the `logind` D-Bus API.
+	Logind,
+}
+
+impl Backend {
+	/// All backends, in the order they are tried when none is specified explicitly.
+	pub const ALL: [Backend; 3] = [Backend::Sysfs, Backend::Ddc, Backend::Logind];
+
+	/// List the names of the controllers available through this backend.
+	pub fn list(self) -> Result<Vec<String>, Error> {
+		match self {
+			Self::Sysfs => sysfs::list(),
+			Self::Ddc => ddc::list(),
+			Self::Logind => logind::list(),
+		}
+	}
+
+	/// Open the controller with the given name through this backend.
+	pub fn open_by_name(self, name: &str) -> Result<Box<dyn BackendController>, Error> {
+		match self {
+			Self::Sysfs => sysfs::open_by_name(name).map(|x| Box::new(x) as Box<dyn BackendController>),
+			Self::Ddc => ddc::open_by_name(name).map(|x| Box::new(x) as Box<dyn BackendController>),
+			Self::Logind => logind::open_by_name(name).map(|x| Box::new(x) as Box<dyn BackendController>),
+		}
+	}
+}
+
+/// List all `(backend, name)` pairs available, restricted to `backend` if given.
+pub fn list_controllers(backend: Option<Backend>) -> Result<Vec<(Backend, String)>, Error> {
+	let mut controllers = Vec::new();
+	for backend in backend.map_or(Backend::ALL.as_slice(), std::slice::from_ref) {
+		for name in backend.list()? {
+			controllers.push((*backend, name));
+		}
+	}
+	Ok(controllers)
+}
+
+/// Open a controller by name, trying every backend if `backend` is not given.
+pub fn open_by_name(backend: Option<Backend>, name: &str) -> Result<Box<dyn BackendController>, Error> {
+	if let Some(backend) = backend {
+		return backend.open_by_name(name);
+	}
+
+	for backend in Backend::ALL {
+		if let Ok(controller) = backend.open_by_name(name) {
+			return Ok(controller);
+		}
+	}
+
+	Err(Error::NamedControllerNotFound { name: name.to_owned() })
+}
+
+/// Open the first working controller, trying every backend in order if `backend` is not given.
+pub fn open_first(backend: Option<Backend>) -> Result<Box<dyn BackendController>, Error> {
+	for backend in backend.map_or(Backend::ALL.as_slice(), std::slice::from_ref) {
+		// A backend that fails to list controllers (for example sysfs when there is no
+		// `/sys/class/backlight` at all) just has none of its own; move on to the next backend
+		// instead of giving up on the whole search.
+		let names = match backend.list() {
+			Ok(names) => names,
+			Err(e) => {
+				log::debug!("Failed to list {backend:?} controllers: {e}");
+				continue;
+			},
+		};
+
+		for name in names {
+			if let Ok(controller) = backend.open_by_name(&name) {
+				log::debug!("Using {backend:?} controller: {name}");
+				return Ok(controller);
+			}
+		}
+	}
+
+	Err(Error::ControllerNotFound)
+}
@@ -0,0 +1,115 @@
+//! Backend that reads and writes `/sys/class/backlight` directly.
+
+use std::path::{Path, PathBuf};
+
+use super::BackendController;
+use crate::Error;
+
+const BACKLIGHT_CONTROLLER_DIR: &str = "/sys/class/backlight";
+
+#[derive(Debug)]
+pub struct SysfsController {
+	name: String,
+	max: u64,
+	value: u64,
+	file: std::fs::File,
+	path: PathBuf,
+}
+
+impl BackendController for SysfsController {
+	fn name(&self) -> &str {
+		&self.name
+	}
+
+	fn max(&self) -> u64 {
+		self.max
+	}
+
+	fn raw(&self) -> u64 {
+		self.value
+	}
+
+	fn set_raw(&mut self, raw: u64) -> Result<(), Error> {
+		use std::io::Write;
+
+		let raw = raw.clamp(0, self.max);
+		self.value = raw;
+		self.file.write_all(raw.to_string().as_bytes())
+			.map_err(|e| Error::Io { path: self.path.clone(), source: e })?;
+		Ok(())
+	}
+}
+
+pub fn list() -> Result<Vec<String>, Error> {
+	let dir = std::fs::read_dir(BACKLIGHT_CONTROLLER_DIR)
+		.map_err(|e| Error::Io { path: PathBuf::from(BACKLIGHT_CONTROLLER_DIR), source: e })?;
+
+	let mut names = Vec::new();
+	for entry in dir {
+		let entry = entry
+			.map_err(|e| Error::Io { path: PathBuf::from(BACKLIGHT_CONTROLLER_DIR), source: e })?;
+		names.push(entry.file_name().to_string_lossy().into_owned());
+	}
+	Ok(names)
+}
+
+pub fn open_by_name(name: &str) -> Result<SysfsController, Error> {
+	open(Path::new(BACKLIGHT_CONTROLLER_DIR).join(name))
+}
+
+/// Read the current value and the maximum value of the controller called `name`, without
+/// requiring write access to its `brightness` file.
+///
+/// This is used by backends (such as [`super::logind`]) that only need sysfs for *reading* the
+/// current state and perform all writes through some other channel.
+pub fn read_only_by_name(name: &str) -> Result<(u64, u64), Error> {
+	let path = Path::new(BACKLIGHT_CONTROLLER_DIR).join(name);
+	let value = open_u64(&path.join("brightness"))?;
+	let max = open_u64(&path.join("max_brightness"))?;
+	Ok((value, max))
+}
+
+fn open(path: impl AsRef<Path>) -> Result<SysfsController, Error> {
+	let path = path.as_ref();
+	log::debug!("Opening controller with path: {}", path.display());
+
+	let name = path.file_name()
+		.map(|x| x.to_string_lossy().into_owned())
+		.unwrap_or_else(|| path.display().to_string());
+
+	let path_max = path.join("max_brightness");
+	let path_brightness = path.join("brightness");
+	let mut file = std::fs::OpenOptions::new()
+		.read(true)
+		.write(true)
+		.create(false)
+		.truncate(false)
+		.open(&path_brightness)
+		.map_err(|e| Error::Io { path: path_brightness.clone(), source: e })?;
+	let value = read_u64(&path_brightness, &mut file)?;
+	let max = open_u64(&path_max)?;
+	Ok(SysfsController {
+		name,
+		max,
+		value,
+		file,
+		path: path_brightness,
+	})
+}
+
+fn open_u64(path: &Path) -> Result<u64, Error> {
+	let mut file = std::fs::File::open(path)
+		.map_err(|e| Error::Io { path: path.to_owned(), source: e })?;
+	read_u64(path, &mut file)
+}
+
+fn read_u64(path: &Path, file: &mut std::fs::File) -> Result<u64, Error> {
+	use std::io::Read;
+	let mut buffer = Vec::new();
+	file.read_to_end(&mut buffer)
+		.map_err(|e| Error::Io { path: path.to_owned(), source: e })?;
+	let data = std::str::from_utf8(&buffer)
+		.map_err(|e| Error::Parse { path: path.to_owned(), source: Box::new(e) })?;
+	data.trim().parse()
+		.map_err(|e: std::num::ParseIntError| Error::Parse { path: path.to_owned(), source: Box::new(e) })
+}
@@ -0,0 +1,85 @@
+//! Configuration file with a default controller, brightness clamps and named presets.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Error;
+
+/// Configuration loaded from `$XDG_CONFIG_HOME/brightness-ctl/config.toml` (or an explicit
+/// `--config` path).
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Config {
+	/// The controller to use when none is given on the command line.
+	#[serde(default)]
+	pub default_controller: Option<String>,
+
+	/// The lowest percentage that `down`/`set`/presets are allowed to reach.
+	#[serde(default)]
+	pub min: Option<f64>,
+
+	/// The highest percentage that `up`/`set`/presets are allowed to reach.
+	#[serde(default)]
+	pub max: Option<f64>,
+
+	/// Named brightness presets, settable with `save-preset` and applied with `preset`.
+	#[serde(default)]
+	pub presets: BTreeMap<String, f64>,
+}
+
+impl Config {
+	/// Load the configuration from `path`, or from the default location if `path` is `None`.
+	///
+	/// A missing file is treated as an empty configuration, not an error.
+	pub fn load(path: Option<&Path>) -> Result<Self, Error> {
+		let path = match path {
+			Some(path) => path.to_owned(),
+			None => default_path()?,
+		};
+
+		match std::fs::read_to_string(&path) {
+			Ok(data) => toml::from_str(&data)
+				.map_err(|e| Error::Parse { path, source: Box::new(e) }),
+			Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+			Err(e) => Err(Error::Io { path, source: e }),
+		}
+	}
+
+	/// Write the configuration back to `path`, or to the default location if `path` is `None`.
+	pub fn save(&self, path: Option<&Path>) -> Result<(), Error> {
+		let path = match path {
+			Some(path) => path.to_owned(),
+			None => default_path()?,
+		};
+
+		if let Some(parent) = path.parent() {
+			std::fs::create_dir_all(parent)
+				.map_err(|e| Error::Io { path: parent.to_owned(), source: e })?;
+		}
+
+		let data = toml::to_string_pretty(self)
+			.map_err(|e| Error::Parse { path: path.clone(), source: Box::new(e) })?;
+		std::fs::write(&path, data)
+			.map_err(|e| Error::Io { path, source: e })
+	}
+
+	/// Clamp a percentage to the configured floor/ceiling, defaulting to the full `0..=100` range.
+	pub fn clamp(&self, value: f64) -> f64 {
+		let min = self.min.unwrap_or(0.0);
+		let max = self.max.unwrap_or(100.0);
+		value.clamp(min, max)
+	}
+}
+
+/// The default configuration file path: `$XDG_CONFIG_HOME/brightness-ctl/config.toml`, falling
+/// back to `$HOME/.config/brightness-ctl/config.toml`.
+fn default_path() -> Result<PathBuf, Error> {
+	if let Some(dir) = std::env::var_os("XDG_CONFIG_HOME") {
+		return Ok(Path::new(&dir).join("brightness-ctl").join("config.toml"));
+	}
+
+	let home = std::env::var_os("HOME")
+		.ok_or_else(|| Error::Backend("neither XDG_CONFIG_HOME nor HOME is set, can not determine the config file location".into()))?;
+	Ok(Path::new(&home).join(".config").join("brightness-ctl").join("config.toml"))
+}
@@ -0,0 +1,53 @@
+//! The error type returned by this crate's public API.
+
+use std::path::PathBuf;
+
+/// Errors that can occur while reading or controlling the brightness of a display.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// No working backlight controller could be found on any backend.
+	#[error("no working backlight controller could be found")]
+	ControllerNotFound,
+
+	/// No controller with the requested name could be found.
+	#[error("no controller named {name:?} could be found")]
+	NamedControllerNotFound {
+		/// The name that was looked up.
+		name: String,
+	},
+
+	/// Failed to read from or write to a file.
+	#[error("failed to access {path}: {source}")]
+	Io {
+		/// The file that could not be accessed.
+		path: PathBuf,
+		/// The underlying I/O error.
+		#[source]
+		source: std::io::Error,
+	},
+
+	/// Failed to parse the contents of a file.
+	#[error("failed to parse {path}: {source}")]
+	Parse {
+		/// The file that could not be parsed.
+		path: PathBuf,
+		/// The underlying parse error.
+		#[source]
+		source: Box<dyn std::error::Error + Send + Sync + 'static>,
+	},
+
+	/// Failed to show a desktop notification.
+	#[error("failed to show notification: {0}")]
+	NotificationFailed(#[source] notify_rust::error::Error),
+
+	/// The requested preset does not exist in the configuration file.
+	#[error("no preset named {name:?} in the configuration file")]
+	PresetNotFound {
+		/// The preset name that was looked up.
+		name: String,
+	},
+
+	/// A backend-specific operation (DDC/CI, logind, ...) failed.
+	#[error("{0}")]
+	Backend(String),
+}
@@ -0,0 +1,101 @@
+//! Interactive brightness adjustment in the terminal.
+
+use crossterm::event::{Event, KeyCode, KeyEventKind};
+
+use brightness_ctl::{Controller, Error};
+
+/// The percentage to nudge the brightness by on each arrow key / +/- press.
+const STEP: f64 = 2.0;
+
+/// Run the interactive brightness adjustment loop.
+///
+/// The terminal is put into raw mode for the duration of the loop and is always restored
+/// afterwards, even if the user aborts with Ctrl-C or an error occurs.
+pub fn run(controller: &mut Controller, perceptual: bool) -> Result<(), Error> {
+	let original = controller.get_percentage(perceptual);
+	let _raw_mode_guard = RawModeGuard::enable()?;
+
+	let result = loop {
+		draw(controller.get_percentage(perceptual));
+
+		match read_key() {
+			Ok(Some(Action::Increase)) => {
+				let value = controller.get_percentage(perceptual) + STEP;
+				let _ = controller.set_percentage(value, perceptual);
+			},
+			Ok(Some(Action::Decrease)) => {
+				let value = controller.get_percentage(perceptual) - STEP;
+				let _ = controller.set_percentage(value, perceptual);
+			},
+			Ok(Some(Action::Commit)) => break Ok(()),
+			Ok(Some(Action::Cancel)) => {
+				let _ = controller.set_percentage(original, perceptual);
+				break Ok(());
+			},
+			Ok(None) => continue,
+			Err(e) => break Err(e),
+		}
+	};
+
+	println!();
+	result
+}
+
+enum Action {
+	Increase,
+	Decrease,
+	Commit,
+	Cancel,
+}
+
+fn read_key() -> Result<Option<Action>, Error> {
+	let event = crossterm::event::read()
+		.map_err(|e| Error::Backend(format!("failed to read a key press: {e}")))?;
+
+	let Event::Key(key) = event else {
+		return Ok(None);
+	};
+	if key.kind != KeyEventKind::Press {
+		return Ok(None);
+	}
+
+	Ok(match key.code {
+		KeyCode::Up | KeyCode::Char('+') => Some(Action::Increase),
+		KeyCode::Down | KeyCode::Char('-') => Some(Action::Decrease),
+		KeyCode::Enter => Some(Action::Commit),
+		KeyCode::Esc | KeyCode::Char('q') => Some(Action::Cancel),
+		KeyCode::Char('c') if key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL) => Some(Action::Cancel),
+		_ => None,
+	})
+}
+
+/// Draw the current percentage and a progress bar on the current terminal line.
+fn draw(percentage: f64) {
+	const WIDTH: usize = 40;
+
+	let filled = ((percentage.clamp(0.0, 100.0) / 100.0) * WIDTH as f64).round() as usize;
+	let bar: String = std::iter::repeat('#').take(filled)
+		.chain(std::iter::repeat('-').take(WIDTH - filled))
+		.collect();
+	print!("\r[{bar}] {percentage:5.1}%  (arrows/+-, Enter to keep, Esc to cancel)");
+	let _ = std::io::Write::flush(&mut std::io::stdout());
+}
+
+/// Puts the terminal into raw mode and restores it again on drop.
+struct RawModeGuard;
+
+impl RawModeGuard {
+	fn enable() -> Result<Self, Error> {
+		crossterm::terminal::enable_raw_mode()
+			.map_err(|e| Error::Backend(format!("failed to enable terminal raw mode: {e}")))?;
+		Ok(Self)
+	}
+}
+
+impl Drop for RawModeGuard {
+	fn drop(&mut self) {
+		if let Err(e) = crossterm::terminal::disable_raw_mode() {
+			log::error!("Failed to restore terminal mode: {e}");
+		}
+	}
+}
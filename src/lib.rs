@@ -0,0 +1,279 @@
+//! Library crate for reading and controlling the brightness of your displays.
+//!
+//! This is the library backing the `brightness-ctl` command line tool. It can also be embedded
+//! in other Rust programs that want to read or change display brightness without shelling out.
+//!
+//! # Example
+//!
+//! ```no_run
+//! let mut controller = brightness_ctl::Controller::open_first(None)?;
+//! controller.set_percentage(50.0, true)?;
+//! # Ok::<(), brightness_ctl::Error>(())
+//! ```
+
+#[cfg(not(target_os = "linux"))]
+compile_error!(concat!(
+	"This tool currently only works on Linux.\n\n",
+	"Support for additional platforms is highly appreciated.\n",
+	"Feel free to open a PR on https://github.com/de-vri-es/brightness-ctl.\n\n",
+));
+
+pub mod backend;
+pub mod config;
+mod error;
+
+pub use error::Error;
+
+use backend::Backend;
+
+/// A brightness controller, wrapping a [`backend::BackendController`] with the perceptual
+/// mapping and fade logic that all backends share.
+#[derive(Debug)]
+pub struct Controller {
+	backend: Box<dyn backend::BackendController>,
+	/// The lowest percentage that `set_percentage`/`fade_to_percentage` are allowed to reach.
+	floor: f64,
+	/// The highest percentage that `set_percentage`/`fade_to_percentage` are allowed to reach.
+	ceiling: f64,
+}
+
+impl Controller {
+	/// Open the controller with the given name, trying every backend if `backend` is `None`.
+	pub fn open_by_name(backend: Option<Backend>, name: &str) -> Result<Self, Error> {
+		Ok(Self { backend: backend::open_by_name(backend, name)?, floor: 0.0, ceiling: 100.0 })
+	}
+
+	/// Open the first working controller, trying every backend in order if `backend` is `None`.
+	pub fn open_first(backend: Option<Backend>) -> Result<Self, Error> {
+		Ok(Self { backend: backend::open_first(backend)?, floor: 0.0, ceiling: 100.0 })
+	}
+
+	/// Restrict the percentage range that `set_percentage`/`fade_to_percentage` can reach.
+	///
+	/// This is how the configuration file's floor/ceiling (see [`config::Config`]) is enforced on
+	/// every write path, including the interactive mode, rather than only in the CLI's `up`/
+	/// `down`/`set`/`preset` handling.
+	pub fn set_clamp(&mut self, min: Option<f64>, max: Option<f64>) {
+		self.floor = min.unwrap_or(0.0);
+		self.ceiling = max.unwrap_or(100.0);
+	}
+
+	/// Set the brightness to `value` immediately.
+	pub fn set_percentage(&mut self, value: f64, perceptual: bool) -> Result<(), Error> {
+		let raw = self.percentage_to_raw(value, perceptual);
+		self.write_raw(raw)
+	}
+
+	/// Fade the brightness from its current raw value to `value` over `duration`, writing
+	/// intermediate values at roughly 60 Hz.
+	///
+	/// The animation is skipped (in favor of a single write) if `duration` is too short to fit
+	/// more than one step, or if the target is barely different from the current value.
+	pub fn fade_to_percentage(&mut self, value: f64, perceptual: bool, duration: std::time::Duration) -> Result<(), Error> {
+		const STEPS_PER_SECOND: f64 = 60.0;
+
+		let start = self.backend.raw();
+		let target = self.percentage_to_raw(value, perceptual);
+
+		let steps = (duration.as_secs_f64() * STEPS_PER_SECOND).round() as i64;
+		if steps <= 1 || start.abs_diff(target) <= 1 {
+			return self.write_raw(target);
+		}
+
+		let step_delay = duration / steps as u32;
+		for i in 0..steps {
+			let t = i as f64 / steps as f64;
+			let eased = ease_in_out_cubic(t);
+			let raw = (start as f64 + (target as f64 - start as f64) * eased).round() as u64;
+			self.write_raw(raw)?;
+			std::thread::sleep(step_delay);
+		}
+
+		// Always finish on the exact target so rounding error can not accumulate.
+		self.write_raw(target)
+	}
+
+	/// Convert a user-facing percentage to a raw value, taking the perceptual mapping and the
+	/// configured floor/ceiling into account.
+	fn percentage_to_raw(&self, value: f64, perceptual: bool) -> u64 {
+		let value = value.clamp(self.floor, self.ceiling);
+		let fraction = if perceptual {
+			perceptual_to_fraction(value)
+		} else {
+			value / 100.0
+		};
+		(fraction * self.backend.max() as f64).round() as u64
+	}
+
+	fn write_raw(&mut self, raw: u64) -> Result<(), Error> {
+		let raw = raw.clamp(0, self.backend.max());
+		self.backend.set_raw(raw)
+	}
+
+	/// Get the current brightness as a percentage.
+	pub fn get_percentage(&self, perceptual: bool) -> f64 {
+		let fraction = self.backend.raw() as f64 / self.backend.max() as f64;
+		if perceptual {
+			fraction_to_perceptual(fraction)
+		} else {
+			fraction * 100.0
+		}
+	}
+}
+
+/// The threshold in fraction-space that corresponds to `lightness == 8.0`, i.e. `8.0 / 903.3`.
+///
+/// Both conversions below must switch branches at exactly this point, or the two pieces don't
+/// meet and round-tripping a value near the boundary introduces a discontinuity.
+const LINEAR_SLOPE_BOUNDARY: f64 = 8.0 / 903.3;
+
+/// Convert a CIE L* lightness (0..100) to a luminance fraction (0.0..1.0).
+fn perceptual_to_fraction(lightness: f64) -> f64 {
+	if lightness > 8.0 {
+		((lightness + 16.0) / 116.0).powi(3)
+	} else {
+		lightness / 903.3
+	}
+}
+
+/// Convert a luminance fraction (0.0..1.0) to a CIE L* lightness (0..100).
+fn fraction_to_perceptual(fraction: f64) -> f64 {
+	if fraction > LINEAR_SLOPE_BOUNDARY {
+		116.0 * fraction.cbrt() - 16.0
+	} else {
+		903.3 * fraction
+	}
+}
+
+/// Cubic ease-in-out: slow start, fast middle, slow end.
+fn ease_in_out_cubic(t: f64) -> f64 {
+	if t < 0.5 {
+		4.0 * t.powi(3)
+	} else {
+		1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// `perceptual_to_fraction` and `fraction_to_perceptual` must be inverses of each other
+	/// everywhere, including right at the piecewise boundary (`p == 8`, `y == 0.008856`).
+	fn assert_round_trip(lightness: f64) {
+		let fraction = perceptual_to_fraction(lightness);
+		let round_tripped = fraction_to_perceptual(fraction);
+		assert!(
+			(round_tripped - lightness).abs() < 1e-6,
+			"round-trip of {lightness} through fraction {fraction} gave {round_tripped}",
+		);
+	}
+
+	#[test]
+	fn perceptual_round_trip_at_extremes() {
+		assert_round_trip(0.0);
+		assert_round_trip(100.0);
+	}
+
+	#[test]
+	fn perceptual_round_trip_at_boundary() {
+		// The piecewise cutoff in `perceptual_to_fraction` is `p == 8`.
+		assert_round_trip(8.0);
+		assert_round_trip(8.0 - 1e-6);
+		assert_round_trip(8.0 + 1e-6);
+	}
+
+	#[test]
+	fn fraction_round_trip_at_boundary() {
+		// The piecewise cutoff in `fraction_to_perceptual` is `y == 0.008856`.
+		let lightness = fraction_to_perceptual(0.008856);
+		assert_round_trip(lightness);
+	}
+
+	#[test]
+	fn perceptual_to_fraction_is_monotonic_and_bounded() {
+		assert_eq!(perceptual_to_fraction(0.0), 0.0);
+		assert!((perceptual_to_fraction(100.0) - 1.0).abs() < 1e-9);
+
+		let mut previous = perceptual_to_fraction(0.0);
+		let mut lightness = 1.0;
+		while lightness <= 100.0 {
+			let fraction = perceptual_to_fraction(lightness);
+			assert!(fraction >= previous, "fraction decreased at lightness {lightness}");
+			previous = fraction;
+			lightness += 1.0;
+		}
+	}
+
+	#[test]
+	fn ease_in_out_cubic_endpoints_and_midpoint() {
+		assert_eq!(ease_in_out_cubic(0.0), 0.0);
+		assert_eq!(ease_in_out_cubic(1.0), 1.0);
+		assert!((ease_in_out_cubic(0.5) - 0.5).abs() < 1e-9);
+	}
+
+	/// A fake [`backend::BackendController`] that just records the raw values it was asked to
+	/// set, so the fade loop can be tested without touching real hardware.
+	#[derive(Debug)]
+	struct MockBackend {
+		max: u64,
+		value: u64,
+		writes: std::rc::Rc<std::cell::RefCell<Vec<u64>>>,
+	}
+
+	impl backend::BackendController for MockBackend {
+		fn name(&self) -> &str {
+			"mock"
+		}
+
+		fn max(&self) -> u64 {
+			self.max
+		}
+
+		fn raw(&self) -> u64 {
+			self.value
+		}
+
+		fn set_raw(&mut self, raw: u64) -> Result<(), Error> {
+			self.value = raw;
+			self.writes.borrow_mut().push(raw);
+			Ok(())
+		}
+	}
+
+	/// Create a controller backed by a [`MockBackend`], along with a handle to the raw values it writes.
+	fn mock_controller(start: u64, max: u64) -> (Controller, std::rc::Rc<std::cell::RefCell<Vec<u64>>>) {
+		let writes = std::rc::Rc::default();
+		let controller = Controller {
+			backend: Box::new(MockBackend { max, value: start, writes: std::rc::Rc::clone(&writes) }),
+			floor: 0.0,
+			ceiling: 100.0,
+		};
+		(controller, writes)
+	}
+
+	#[test]
+	fn fade_always_ends_on_the_exact_target() {
+		let (mut controller, _writes) = mock_controller(0, 1000);
+		controller.fade_to_percentage(37.0, false, std::time::Duration::from_millis(200)).unwrap();
+		assert_eq!(controller.backend.raw(), 370);
+	}
+
+	#[test]
+	fn fade_is_skipped_for_a_tiny_duration() {
+		let (mut controller, writes) = mock_controller(0, 1000);
+		controller.fade_to_percentage(50.0, false, std::time::Duration::ZERO).unwrap();
+		assert_eq!(controller.backend.raw(), 500);
+		assert_eq!(*writes.borrow(), [500]);
+	}
+
+	#[test]
+	fn fade_is_skipped_for_a_tiny_delta() {
+		let (mut controller, writes) = mock_controller(500, 1000);
+		// 50.1% maps to raw 501, within one raw unit of the current 500/1000, so the fade should
+		// collapse to a single write instead of stepping.
+		controller.fade_to_percentage(50.1, false, std::time::Duration::from_millis(200)).unwrap();
+		assert_eq!(controller.backend.raw(), 501);
+		assert_eq!(*writes.borrow(), [501]);
+	}
+}
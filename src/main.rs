@@ -14,18 +14,15 @@
 //! brightness-ctl --help
 //! ```
 
-#[cfg(not(target_os = "linux"))]
-compile_error!(concat!(
-	"This tool currently only works on Linux.\n\n",
-	"Support for additional platforms is highly appreciated.\n",
-	"Feel free to open a PR on https://github.com/de-vri-es/brightness-ctl.\n\n",
-));
+mod interactive;
 
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 
 use notify_rust::Notification;
 
-const BACKLIGHT_CONTROLLER_DIR: &str = "/sys/class/backlight";
+use brightness_ctl::backend::Backend;
+use brightness_ctl::config::Config;
+use brightness_ctl::{Controller, Error};
 
 /// Set or get the brightness of your display.
 #[derive(clap::Parser)]
@@ -52,6 +49,39 @@ struct Options {
 	#[clap(global = true)]
 	controller: Option<String>,
 
+	/// The backend to use to find and control the controller.
+	///
+	/// If not specified, all backends are tried in order: sysfs, ddc, logind.
+	#[clap(long, short)]
+	#[clap(global = true)]
+	backend: Option<Backend>,
+
+	/// The configuration file to use.
+	///
+	/// Defaults to `$XDG_CONFIG_HOME/brightness-ctl/config.toml`.
+	#[clap(long)]
+	#[clap(global = true)]
+	config: Option<PathBuf>,
+
+	/// Use a linear raw-value mapping instead of a perceptual (CIE L*) one.
+	///
+	/// By default, the percentage given on the command line is treated as a perceptual
+	/// lightness (CIE L*), so that equal steps look equally large across the whole range.
+	/// Pass this flag to fall back to the old behaviour of mapping the percentage linearly
+	/// onto the raw brightness value.
+	#[clap(long)]
+	#[clap(global = true)]
+	linear: bool,
+
+	/// Fade to the target brightness over the given duration in milliseconds, instead of
+	/// jumping to it immediately.
+	///
+	/// Set this to 0 to change the brightness in a single step.
+	#[clap(long, short)]
+	#[clap(global = true)]
+	#[clap(default_value = "200")]
+	fade: u64,
+
 	/// The subcommand to execute.
 	#[clap(subcommand)]
 	command: Command,
@@ -80,20 +110,41 @@ enum Command {
 		value: f64,
 	},
 
-	/// Print the current screen brightness as a percentage.
+	/// Print the current screen brightness as a linear percentage.
+	///
+	/// This always uses the linear (non-perceptual) scale, regardless of `--linear`, so scripts
+	/// parsing the output keep seeing the same numbers as before the perceptual mode existed.
 	Get,
 
 	/// Print a list of screen brightness controllers.
 	ListControllers,
+
+	/// Interactively adjust the brightness with the arrow keys or +/-.
+	///
+	/// Press Enter to keep the new brightness, or Esc to restore the original value.
+	Interactive,
+
+	/// Set the brightness to a named preset from the configuration file.
+	Preset {
+		/// The name of the preset to apply.
+		name: String,
+	},
+
+	/// Save the current brightness as a named preset in the configuration file.
+	SavePreset {
+		/// The name to save the preset under.
+		name: String,
+	},
 }
 
 fn main() {
-	if let Err(()) = do_main(clap::Parser::parse()) {
+	if let Err(e) = do_main(clap::Parser::parse()) {
+		log::error!("{e}");
 		std::process::exit(1);
 	}
 }
 
-fn do_main(options: Options) -> Result<(), ()> {
+fn do_main(options: Options) -> Result<(), Error> {
 	env_logger::Builder::new()
 		.filter_module(module_path!(), log_level(options.verbose, options.quiet))
 		.format_timestamp(None)
@@ -102,130 +153,60 @@ fn do_main(options: Options) -> Result<(), ()> {
 		.init();
 
 	if let Command::ListControllers = options.command {
-		for controller in Controller::list()? {
-			if let Some(name) = controller.file_name().map(|x| x.to_string_lossy()) {
-				println!("{name}");
-			}
+		for (backend, name) in brightness_ctl::backend::list_controllers(options.backend)? {
+			println!("{name} ({backend:?})");
 		}
 		return Ok(())
 	}
 
-	let mut controller = match &options.controller {
-		Some(name) => Controller::open_by_name(name)?,
-		None => Controller::open_first()?,
-	};
-
-	let mut brightness = controller.get_percentage();
-	match options.command {
-		Command::Up { value } => brightness += value,
-		Command::Down { value } => brightness -= value,
-		Command::Set { value } => brightness = value,
-		Command::Get => {
-			println!("{brightness:.0}");
-			return Ok(())
-		},
-		Command::ListControllers => unreachable!(),
-	}
-
-	controller.set_percentage(brightness)?;
-	show_notification(controller.get_percentage());
-	Ok(())
-}
+	let mut config = Config::load(options.config.as_deref())?;
 
-#[derive(Debug)]
-struct Controller{
-	max: u64,
-	value: u64,
-	file: std::fs::File,
-	path: PathBuf,
-}
+	let controller_name = options.controller.clone().or_else(|| config.default_controller.clone());
+	let mut controller = match &controller_name {
+		Some(name) => Controller::open_by_name(options.backend, name)?,
+		None => Controller::open_first(options.backend)?,
+	};
+	controller.set_clamp(config.min, config.max);
 
-impl Controller {
-	fn open(path: impl AsRef<Path>) -> Result<Self, ()> {
-		let path = path.as_ref();
-		log::debug!("Opening controller with path: {}", path.display());
-
-		let path_max = path.join("max_brightness");
-		let path_brightness = path.join("brightness");
-		let mut file = std::fs::OpenOptions::new()
-			.read(true)
-			.write(true)
-			.create(false)
-			.truncate(false)
-			.open(&path_brightness)
-			.map_err(|e| log::error!("Failed to open {} for reading and writing: {e}", path_brightness.display()))?;
-		let value = read_u64(&path_brightness, &mut file)?;
-		let max = open_u64(&path_max)?;
-		Ok(Self {
-			max,
-			value,
-			file,
-			path: path_brightness,
-		})
-	}
+	let perceptual = !options.linear;
+	let fade = std::time::Duration::from_millis(options.fade);
 
-	fn open_by_name(name: &str) -> Result<Self, ()> {
-		Self::open(Path::new(BACKLIGHT_CONTROLLER_DIR).join(name))
+	if let Command::Interactive = options.command {
+		return interactive::run(&mut controller, perceptual);
 	}
 
-	fn open_first() -> Result<Self, ()> {
-		for path in Self::list()? {
-			if let Ok(x) = Self::open(&path) {
-				log::debug!("Using controller at {}", path.display());
-				return Ok(x);
-			}
-		}
-
-		log::error!("Failed to find any working congroller");
-		Err(())
+	if let Command::SavePreset { name } = options.command {
+		config.presets.insert(name, controller.get_percentage(perceptual));
+		return config.save(options.config.as_deref());
 	}
 
-	fn list() -> Result<impl Iterator<Item = PathBuf>, ()> {
-		let path = BACKLIGHT_CONTROLLER_DIR;
-		let dir = std::fs::read_dir(path)
-			.map_err(|e| log::error!("Failed to open directory {path}: {e}"))?;
-		Ok(dir.filter_map(move |entry| {
-			let entry = entry
-				.map_err(|e| log::error!("Failed to read entry of {path}: {e}"))
-				.ok()?;
-			Some(entry.path())
-		}))
+	if let Command::Get = options.command {
+		// Always report the linear percentage here, regardless of --linear: scripts that parse
+		// `get`'s output predate the perceptual mapping and expect the old raw-linear scale.
+		println!("{:.0}", controller.get_percentage(false));
+		return Ok(())
 	}
 
-	fn set_percentage(&mut self, value: f64) -> Result<(), ()> {
-		use std::io::Write;
-
-		let raw = (value / 100.0 * self.max as f64).round() as u64;
-		let raw = raw.clamp(0, self.max);
-		self.value = raw;
-		self.file.write_all(raw.to_string().as_bytes())
-			.map_err(|e| log::error!("Failed to write to {}: {e}", self.path.display()))?;
-		Ok(())
+	let mut brightness = controller.get_percentage(perceptual);
+	match options.command {
+		Command::Up { value } => brightness += value,
+		Command::Down { value } => brightness -= value,
+		Command::Set { value } => brightness = value,
+		Command::Preset { name } => {
+			brightness = *config.presets.get(&name)
+				.ok_or_else(|| Error::PresetNotFound { name: name.clone() })?;
+		},
+		Command::Get | Command::ListControllers | Command::Interactive | Command::SavePreset { .. } => unreachable!(),
 	}
 
-	fn get_percentage(&self) -> f64 {
-		self.value as f64 / self.max as f64 * 100.0
+	controller.fade_to_percentage(brightness, perceptual, fade)?;
+	if let Err(e) = show_notification(controller.get_percentage(perceptual)) {
+		log::error!("{e}");
 	}
+	Ok(())
 }
 
-fn open_u64(path: &Path) -> Result<u64, ()> {
-	let mut file = std::fs::File::open(path)
-		.map_err(|e| log::error!("Failed to open {}: {e}", path.display()))?;
-	read_u64(path, &mut file)
-}
-
-fn read_u64(path: &Path, file: &mut std::fs::File) -> Result<u64, ()> {
-	use std::io::Read;
-	let mut buffer = Vec::new();
-	file.read_to_end(&mut buffer)
-		.map_err(|e| log::error!("Failed to read from {}: {e}", path.display()))?;
-	let data = std::str::from_utf8(&buffer)
-		.map_err(|e| log::error!("Invalid UTF-8 in {}: {e}", path.display()))?;
-	data.trim().parse()
-		.map_err(|e| log::error!("Failed to parse {}: {e}", path.display()))
-}
-
-fn show_notification(percentage: f64) {
+fn show_notification(percentage: f64) -> Result<(), Error> {
 	let mut notification = Notification::new();
 	notification.summary(&format!("Screen brightness: {percentage:.0}%"));
 	notification.icon("display-brightness-symbolic");
@@ -233,8 +214,8 @@ fn show_notification(percentage: f64) {
 	#[cfg(all(unix, not(target_os = "macos")))]
 	notification.hint(notify_rust::Hint::CustomInt("value".to_owned(), percentage.round() as i32));
 	notification.show()
-		.map_err(|e| log::error!("Failed to show notification: {e}"))
-		.ok();
+		.map_err(Error::NotificationFailed)?;
+	Ok(())
 }
 
 /// Create a colorful style for the command line interface.